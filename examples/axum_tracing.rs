@@ -1,60 +1,78 @@
-use std::time::Duration;
+use std::{net::SocketAddr, time::Duration};
 
 use anyhow::{Ok, Result};
-use axum::{routing::get, Router};
-use opentelemetry::KeyValue;
+use axum::{middleware, routing::get, Router};
+use ecosystem::{
+    access_log::AccessLogLayer,
+    logging_config::{level_admin_router, level_layer, LoggingConfig},
+    metrics::{init_meter, resource, track_metrics, HttpMetrics},
+};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::{
     runtime,
     trace::{self, RandomIdGenerator, Tracer},
-    Resource,
 };
 use tokio::{
     join,
     net::TcpListener,
     time::{sleep, Instant},
 };
-use tracing::{debug, info, instrument, level_filters::LevelFilter, warn};
+use tracing::{debug, info, instrument, warn};
 use tracing_subscriber::{
     fmt::{self, format::FmtSpan},
     layer::SubscriberExt,
     util::SubscriberInitExt,
-    Layer,
+    Registry,
 };
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let logging = LoggingConfig::load("axum-tracing.toml").unwrap_or_default();
+    let (level, level_handle) = level_layer::<Registry>(logging.level);
+
     // console layer
     let console = fmt::Layer::new()
         .with_span_events(FmtSpan::CLOSE)
-        .pretty()
-        .with_filter(LevelFilter::DEBUG);
+        .pretty();
 
     // file appender layer
     let file_appender = tracing_appender::rolling::daily("logs", "ecosystem.log");
     let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
 
-    let file = fmt::Layer::new()
-        .with_writer(non_blocking)
-        .pretty()
-        .with_filter(LevelFilter::INFO);
+    let file = fmt::Layer::new().with_writer(non_blocking).pretty();
 
     // opentelemetry tracing layer
     let tracer = init_tracer()?;
     let opentelemetry = tracing_opentelemetry::layer().with_tracer(tracer);
 
     tracing_subscriber::registry()
+        .with(level)
         .with(console)
         .with(file)
         .with(opentelemetry)
         .init();
 
+    let (meter, metrics_router) = init_meter("axum-tracing", Some("http://localhost:4317"))?;
+    let http_metrics = HttpMetrics::new(&meter);
+
     let addr = "0.0.0.0:8080";
-    let app = Router::new().route("/", get(index_handler));
+    let app = Router::new()
+        .route("/", get(index_handler))
+        .layer(AccessLogLayer::new(logging))
+        .route_layer(middleware::from_fn_with_state(
+            http_metrics,
+            track_metrics,
+        ))
+        .merge(metrics_router)
+        .merge(level_admin_router(level_handle));
 
     let listener = TcpListener::bind(addr).await?;
     info!("Listening server on: {}", addr);
-    axum::serve(listener, app.into_make_service()).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -113,10 +131,7 @@ fn init_tracer() -> Result<Tracer> {
                 .with_id_generator(RandomIdGenerator::default())
                 .with_max_events_per_span(32)
                 .with_max_attributes_per_span(64)
-                .with_resource(Resource::new(vec![KeyValue::new(
-                    "service.name",
-                    "axum-tracing",
-                )])),
+                .with_resource(resource("axum-tracing")),
         )
         .install_batch(runtime::Tokio)?;
     Ok(tracer)
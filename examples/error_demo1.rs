@@ -16,6 +16,10 @@ fn main() -> Result<(), std::io::Error> {
     Ok(())
 }
 
+// The explicit match below is what `?` desugars to (see the comment at the
+// top of this file) — keep it as-is for the demo rather than letting clippy
+// collapse it back into the `?` it's meant to illustrate.
+#[allow(clippy::question_mark)]
 fn read_username(path: &str) -> Result<String, std::io::Error> {
     let username_file_result = File::open(path);
     let mut username_file: File = match username_file_result {
@@ -2,6 +2,8 @@ use anyhow::{Ok, Result};
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
+// Toy `Layer`/`Visit` impl kept around for learning purposes; the real thing
+// that does something with the fields it collects is `ecosystem::ndjson_log`.
 struct CustomLayer;
 
 impl<S> Layer<S> for CustomLayer
@@ -0,0 +1,269 @@
+use std::{
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Instant,
+};
+
+use axum::extract::ConnectInfo;
+use http::{Request, Response};
+use pin_project::{pin_project, pinned_drop};
+use tower::{Layer, Service};
+use tracing::{info, warn, Span};
+use uuid::Uuid;
+
+use crate::logging_config::LoggingConfig;
+
+/// `tower::Layer` that gives every request passing through it a uniform
+/// access log: a `tracing` span carrying method/path/request-id, an optional
+/// "request received" event, and an optional structured summary event
+/// emitted when the response is produced (or, regardless of config, a
+/// `warn` event if the request future is dropped/cancelled beforehand).
+///
+/// Shared by the url-shortener and axum-tracing services so neither has to
+/// sprinkle ad-hoc `info!`/`warn!` calls through its handlers. Whether the
+/// two events fire is governed by `LoggingConfig` so the toggles work
+/// uniformly across both.
+#[derive(Debug, Clone, Default)]
+pub struct AccessLogLayer {
+    config: LoggingConfig,
+}
+
+impl AccessLogLayer {
+    pub fn new(config: LoggingConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer {
+    type Service = AccessLog<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLog {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessLog<S> {
+    inner: S,
+    config: LoggingConfig,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for AccessLog<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let client_addr = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let request_id = Uuid::new_v4();
+
+        let span = tracing::info_span!(
+            "request",
+            method = %req.method(),
+            path = %req.uri().path(),
+            %request_id,
+            %client_addr,
+        );
+
+        if self.config.log_requests {
+            let _enter = span.enter();
+            info!("request received");
+        }
+
+        ResponseFuture {
+            inner: self.inner.call(req),
+            span,
+            start: Instant::now(),
+            completed: false,
+            log_completed: self.config.log_completed_requests,
+        }
+    }
+}
+
+/// Future returned by [`AccessLog`]. Records wall-clock latency from the
+/// moment [`AccessLog::call`] builds this future, not from its first poll,
+/// and logs the outcome at most once: when the inner future resolves via
+/// [`Future::poll`] (a "request completed" summary only if
+/// `log_completed_requests` is set, a "request failed" warning on error), or
+/// on cancellation via the `PinnedDrop` guard below.
+#[pin_project(PinnedDrop)]
+pub struct ResponseFuture<F> {
+    #[pin]
+    inner: F,
+    span: Span,
+    start: Instant,
+    completed: bool,
+    log_completed: bool,
+}
+
+impl<F, B, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+{
+    type Output = Result<Response<B>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let _enter = this.span.enter();
+
+        let output = ready!(this.inner.as_mut().poll(cx));
+        *this.completed = true;
+
+        let latency_ms = this.start.elapsed().as_millis() as u64;
+        match &output {
+            Ok(res) if *this.log_completed => {
+                info!(status = res.status().as_u16(), latency_ms, "request completed");
+            }
+            Ok(_) => {}
+            Err(_) => {
+                warn!(latency_ms, "request failed");
+            }
+        }
+
+        Poll::Ready(output)
+    }
+}
+
+#[pinned_drop]
+impl<F> PinnedDrop for ResponseFuture<F> {
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        if !*this.completed {
+            let _enter = this.span.enter();
+            warn!(
+                latency_ms = this.start.elapsed().as_millis() as u64,
+                "request cancelled before completion"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::{Arc, Mutex},
+        task::{RawWaker, RawWakerVTable, Waker},
+    };
+
+    use tower::service_fn;
+    use tracing::{field::Field, Event, Level};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    /// Records every event's level and `message` field so tests can assert
+    /// on what the access-log middleware actually logged.
+    #[derive(Clone, Default)]
+    struct CapturedEvents(Arc<Mutex<Vec<(Level, String)>>>);
+
+    impl CapturedEvents {
+        fn any_warn_containing(&self, needle: &str) -> bool {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(level, msg)| *level == Level::WARN && msg.contains(needle))
+        }
+    }
+
+    struct MessageVisitor(String);
+
+    impl tracing::field::Visit for MessageVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                self.0 = format!("{value:?}");
+            }
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for CapturedEvents
+    where
+        S: tracing::Subscriber,
+    {
+        fn on_event(&self, event: &Event<'_>, _ctx: tracing_subscriber::layer::Context<'_, S>) {
+            let mut visitor = MessageVisitor(String::new());
+            event.record(&mut visitor);
+            self.0
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), visitor.0));
+        }
+    }
+
+    fn noop_waker() -> Waker {
+        fn no_op(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker {
+            raw_waker()
+        }
+        fn raw_waker() -> RawWaker {
+            RawWaker::new(
+                std::ptr::null(),
+                &RawWakerVTable::new(clone, no_op, no_op, no_op),
+            )
+        }
+        unsafe { Waker::from_raw(raw_waker()) }
+    }
+
+    #[test]
+    fn dropping_an_incomplete_future_logs_a_warning() {
+        let events = CapturedEvents::default();
+        let subscriber = tracing_subscriber::registry().with(events.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let mut service = AccessLogLayer::new(LoggingConfig::default()).layer(service_fn(
+                |_req: Request<()>| std::future::pending::<Result<Response<()>, Infallible>>(),
+            ));
+
+            let req = Request::builder().body(()).unwrap();
+            let mut future = Box::pin(Service::call(&mut service, req));
+
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            assert!(future.as_mut().poll(&mut cx).is_pending());
+
+            drop(future);
+        });
+
+        assert!(events.any_warn_containing("cancelled before completion"));
+    }
+
+    #[test]
+    fn a_completed_future_does_not_log_a_cancellation_warning() {
+        let events = CapturedEvents::default();
+        let subscriber = tracing_subscriber::registry().with(events.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let mut service = AccessLogLayer::new(LoggingConfig::default()).layer(service_fn(
+                |_req: Request<()>| async { Ok::<_, Infallible>(Response::new(())) },
+            ));
+
+            let req = Request::builder().body(()).unwrap();
+            let mut future = Box::pin(Service::call(&mut service, req));
+
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            assert!(future.as_mut().poll(&mut cx).is_ready());
+
+            drop(future);
+        });
+
+        assert!(!events.any_warn_containing("cancelled before completion"));
+    }
+}
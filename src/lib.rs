@@ -0,0 +1,5 @@
+pub mod access_log;
+pub mod logging_config;
+pub mod metrics;
+pub mod ndjson_log;
+pub mod shortener;
@@ -0,0 +1,133 @@
+use std::{path::Path, str::FromStr};
+
+use axum::{extract::State, routing::put, Router};
+use http::StatusCode;
+use serde::Deserialize;
+use tracing::{level_filters::LevelFilter, Subscriber};
+use tracing_subscriber::reload;
+
+/// Logging behavior an operator can flip without redeploying: loaded once at
+/// startup from a `[logging]` table in a TOML file, overlaid with
+/// `ECOSYSTEM_LOG_*` env vars, and (for `level`) mutable afterwards through
+/// the [`reload::Handle`] returned by [`level_layer`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoggingConfig {
+    /// Whether the access-log middleware logs a request as it comes in.
+    #[serde(default = "default_true")]
+    pub log_requests: bool,
+    /// Whether the access-log middleware emits a separate summary event
+    /// (status + duration) when a request completes.
+    #[serde(default = "default_true")]
+    pub log_completed_requests: bool,
+    /// Console/file/OTLP filter level, shared by every layer via the
+    /// `reload::Handle` returned by [`level_layer`].
+    #[serde(default = "default_level", with = "level_filter_serde")]
+    pub level: LevelFilter,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            log_requests: default_true(),
+            log_completed_requests: default_true(),
+            level: default_level(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_level() -> LevelFilter {
+    LevelFilter::INFO
+}
+
+impl LoggingConfig {
+    /// Loads the `[logging]` table from the TOML file at `path` if it
+    /// exists, falling back to defaults otherwise, then overlays
+    /// `ECOSYSTEM_LOG_REQUESTS`, `ECOSYSTEM_LOG_COMPLETED_REQUESTS` and
+    /// `ECOSYSTEM_LOG_LEVEL` from the environment.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        #[derive(Deserialize, Default)]
+        struct File {
+            #[serde(default)]
+            logging: Option<LoggingConfig>,
+        }
+
+        let mut config = match std::fs::read_to_string(path) {
+            Ok(toml) => toml::from_str::<File>(&toml)?.logging.unwrap_or_default(),
+            Err(_) => Self::default(),
+        };
+
+        if let Ok(v) = std::env::var("ECOSYSTEM_LOG_REQUESTS") {
+            config.log_requests = parse_bool(&v)?;
+        }
+        if let Ok(v) = std::env::var("ECOSYSTEM_LOG_COMPLETED_REQUESTS") {
+            config.log_completed_requests = parse_bool(&v)?;
+        }
+        if let Ok(v) = std::env::var("ECOSYSTEM_LOG_LEVEL") {
+            config.level = LevelFilter::from_str(&v)
+                .map_err(|_| anyhow::anyhow!("invalid ECOSYSTEM_LOG_LEVEL: {v}"))?;
+        }
+
+        Ok(config)
+    }
+}
+
+fn parse_bool(v: &str) -> anyhow::Result<bool> {
+    v.parse()
+        .map_err(|_| anyhow::anyhow!("expected a bool, got {v}"))
+}
+
+mod level_filter_serde {
+    use std::str::FromStr;
+
+    use serde::{de::Error, Deserialize, Deserializer};
+    use tracing::level_filters::LevelFilter;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<LevelFilter, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        LevelFilter::from_str(&s).map_err(D::Error::custom)
+    }
+}
+
+/// Wraps `initial` in a [`reload::Layer`] so the global level filter can be
+/// swapped at runtime (via the admin router built by [`level_admin_router`])
+/// through the returned handle, without restarting the process.
+pub fn level_layer<S>(initial: LevelFilter) -> (reload::Layer<LevelFilter, S>, reload::Handle<LevelFilter, S>)
+where
+    S: Subscriber,
+{
+    reload::Layer::new(initial)
+}
+
+/// `/admin/log-level` router backed by `handle`: a `PUT` with a plain-text
+/// body (e.g. `debug`) swaps the live level filter via
+/// [`reload::Handle::modify`], so verbosity can change without a restart.
+/// Merge into a service's router alongside its `/metrics` router.
+pub fn level_admin_router<S>(handle: reload::Handle<LevelFilter, S>) -> Router
+where
+    S: Subscriber + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/admin/log-level", put(set_level::<S>))
+        .with_state(handle)
+}
+
+async fn set_level<S>(
+    State(handle): State<reload::Handle<LevelFilter, S>>,
+    body: String,
+) -> Result<(), (StatusCode, String)>
+where
+    S: Subscriber + Send + Sync + 'static,
+{
+    let level = LevelFilter::from_str(body.trim())
+        .map_err(|_| (StatusCode::BAD_REQUEST, format!("invalid log level: {body}")))?;
+    handle
+        .modify(|filter| *filter = level)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
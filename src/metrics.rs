@@ -0,0 +1,163 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use http::header::CONTENT_TYPE;
+use opentelemetry::{
+    metrics::{Counter, Histogram, Meter, MeterProvider, Unit},
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    metrics::{PeriodicReader, SdkMeterProvider},
+    runtime, Resource,
+};
+use prometheus::{Encoder, Registry, TextEncoder};
+
+/// `Resource` shared by the tracer (see `init_tracer` in the axum-tracing
+/// example) and the meter built here, so traces and metrics emitted by the
+/// same process correlate on `service.name`.
+pub fn resource(service_name: &str) -> Resource {
+    Resource::new(vec![KeyValue::new(
+        "service.name",
+        service_name.to_string(),
+    )])
+}
+
+/// Request-level instruments recorded by [`track_metrics`] for every route.
+#[derive(Debug, Clone)]
+pub struct HttpMetrics {
+    requests: Counter<u64>,
+    latency: Histogram<f64>,
+}
+
+impl HttpMetrics {
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            requests: meter
+                .u64_counter("http.server.requests")
+                .with_description("Number of HTTP requests received, labeled by route and status")
+                .init(),
+            latency: meter
+                .f64_histogram("http.server.latency")
+                .with_description("HTTP request latency in seconds, labeled by route")
+                .with_unit(Unit::new("s"))
+                .init(),
+        }
+    }
+}
+
+/// Shortener-specific counters: how ids get resolved, and how often
+/// content-addressed id derivation has to extend its digest prefix.
+#[derive(Debug, Clone)]
+pub struct ShortenerMetrics {
+    pub urls_created: Counter<u64>,
+    pub urls_looked_up: Counter<u64>,
+    pub id_retries: Counter<u64>,
+}
+
+impl ShortenerMetrics {
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            urls_created: meter
+                .u64_counter("shortener.urls_created")
+                .with_description("Number of URLs assigned a new short id")
+                .init(),
+            urls_looked_up: meter
+                .u64_counter("shortener.urls_looked_up")
+                .with_description("Number of shorten requests resolved to an existing id")
+                .init(),
+            id_retries: meter
+                .u64_counter("shortener.id_retries")
+                .with_description("Number of times id generation had to retry after a collision")
+                .init(),
+        }
+    }
+}
+
+/// Axum middleware (used via `middleware::from_fn_with_state`) that records
+/// the request counter and latency histogram in [`HttpMetrics`] for every
+/// request, labeled by route and response status.
+///
+/// Labels by the matched route pattern (e.g. `/:id`), not the concrete
+/// request path, so path parameters don't blow up label cardinality.
+pub async fn track_metrics(
+    State(metrics): State<HttpMetrics>,
+    matched_path: Option<MatchedPath>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let route = matched_path
+        .map(|matched_path| matched_path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let start = Instant::now();
+
+    let response = next.run(req).await;
+
+    let labels = [
+        KeyValue::new("route", route),
+        KeyValue::new("status", response.status().as_u16().to_string()),
+    ];
+    metrics.requests.add(1, &labels);
+    metrics.latency.record(start.elapsed().as_secs_f64(), &labels);
+
+    response
+}
+
+/// Builds a meter that feeds a pull-based Prometheus registry, returning the
+/// [`Meter`] to derive instruments from and a `/metrics` router to merge
+/// into the service's own. When `otlp_endpoint` is `Some`, the same meter
+/// also periodically pushes to an OTLP collector at that endpoint, so the
+/// two exporters stay independently configurable — the Prometheus endpoint
+/// is always on, the OTLP push is opt-in.
+pub fn init_meter(
+    service_name: &str,
+    otlp_endpoint: Option<&str>,
+) -> anyhow::Result<(Meter, Router)> {
+    let registry = Registry::new();
+    let prometheus_reader = opentelemetry_prometheus::exporter()
+        .with_registry(registry.clone())
+        .build()?;
+
+    let mut provider_builder = SdkMeterProvider::builder()
+        .with_reader(prometheus_reader)
+        .with_resource(resource(service_name));
+
+    if let Some(endpoint) = otlp_endpoint {
+        let aggregation_selector =
+            Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new());
+        let temporality_selector =
+            Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new());
+        let otlp_exporter = opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(endpoint)
+            .build_metrics_exporter(aggregation_selector, temporality_selector)?;
+        let otlp_reader = PeriodicReader::builder(otlp_exporter, runtime::Tokio).build();
+        provider_builder = provider_builder.with_reader(otlp_reader);
+    }
+
+    let provider = provider_builder.build();
+    let meter = provider.meter(service_name.to_string());
+    opentelemetry::global::set_meter_provider(provider);
+
+    let router = Router::new()
+        .route("/metrics", get(scrape))
+        .with_state(registry);
+
+    Ok((meter, router))
+}
+
+async fn scrape(State(registry): State<Registry>) -> impl IntoResponse {
+    let metric_families = registry.gather();
+    let mut buf = Vec::new();
+    TextEncoder::new()
+        .encode(&metric_families, &mut buf)
+        .expect("prometheus text encoding is infallible for well-formed metric families");
+
+    ([(CONTENT_TYPE, "text/plain; version=0.0.4")], buf)
+}
@@ -0,0 +1,280 @@
+use std::{collections::HashSet, fmt, io::Write};
+
+use serde_json::{json, Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Subscriber,
+};
+use tracing_subscriber::{fmt::MakeWriter, layer::Context, registry::LookupSpan, Layer};
+
+/// Field-level redaction applied before an event is serialized, so e.g. the
+/// url shortener can log events without leaking a full target URL.
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    dropped: HashSet<&'static str>,
+    masked: HashSet<&'static str>,
+}
+
+impl Redaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Omits the field entirely from the serialized event.
+    pub fn drop_field(mut self, name: &'static str) -> Self {
+        self.dropped.insert(name);
+        self
+    }
+
+    /// Replaces the field's value with `"***"`.
+    pub fn mask_field(mut self, name: &'static str) -> Self {
+        self.masked.insert(name);
+        self
+    }
+
+    fn apply(&self, name: &str, value: Value) -> Option<Value> {
+        if self.dropped.contains(name) {
+            None
+        } else if self.masked.contains(name) {
+            Some(json!("***"))
+        } else {
+            Some(value)
+        }
+    }
+}
+
+/// `tracing_subscriber::Layer` that serializes every event as one
+/// newline-delimited JSON object carrying timestamp, level, target, the
+/// current span's name, the full (inherited) span field context, and the
+/// event's own fields. Generic over any `MakeWriter` so it composes with the
+/// `tracing_appender` non-blocking writers already used elsewhere.
+///
+/// This promotes the `CustomLayer`/`PrintlnVisitor` demo in the
+/// `tracing_custom` example from println debugging into something worth
+/// shipping logs through.
+pub struct NdjsonLayer<W> {
+    make_writer: W,
+    redaction: Redaction,
+}
+
+impl<W> NdjsonLayer<W>
+where
+    W: for<'writer> MakeWriter<'writer> + 'static,
+{
+    pub fn new(make_writer: W) -> Self {
+        Self {
+            make_writer,
+            redaction: Redaction::default(),
+        }
+    }
+
+    pub fn with_redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+}
+
+/// Accumulated, already-redacted fields for a span, inherited from its
+/// parent at creation time and stored in the span's extensions so `on_event`
+/// doesn't have to walk the scope chain on every event.
+struct SpanFields(Map<String, Value>);
+
+struct JsonVisitor<'a> {
+    redaction: &'a Redaction,
+    fields: Map<String, Value>,
+}
+
+impl JsonVisitor<'_> {
+    fn record(&mut self, field: &Field, value: Value) {
+        if let Some(value) = self.redaction.apply(field.name(), value) {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, json!(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, json!(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.record(field, json!(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, json!(format!("{value:?}")));
+    }
+}
+
+impl<S, W> Layer<S> for NdjsonLayer<W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'writer> MakeWriter<'writer> + 'static,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("span must exist in on_new_span");
+
+        let mut fields = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<SpanFields>().map(|f| f.0.clone()))
+            .unwrap_or_default();
+
+        let mut visitor = JsonVisitor {
+            redaction: &self.redaction,
+            fields: Map::new(),
+        };
+        attrs.record(&mut visitor);
+        fields.extend(visitor.fields);
+
+        span.extensions_mut().insert(SpanFields(fields));
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let (span_name, mut fields) = match ctx.lookup_current() {
+            Some(span) => {
+                let fields = span
+                    .extensions()
+                    .get::<SpanFields>()
+                    .map(|f| f.0.clone())
+                    .unwrap_or_default();
+                (Some(span.name()), fields)
+            }
+            None => (None, Map::new()),
+        };
+
+        let mut visitor = JsonVisitor {
+            redaction: &self.redaction,
+            fields: Map::new(),
+        };
+        event.record(&mut visitor);
+        fields.extend(visitor.fields);
+
+        let metadata = event.metadata();
+        let line = json!({
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "level": metadata.level().to_string(),
+            "target": metadata.target(),
+            "span": span_name,
+            "fields": fields,
+        });
+
+        let mut writer = self.make_writer.make_writer();
+        let _ = writeln!(writer, "{line}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    /// `MakeWriter` over a shared buffer, so tests can inspect what the
+    /// layer actually wrote.
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'writer> MakeWriter<'writer> for SharedBuf {
+        type Writer = SharedBuf;
+
+        fn make_writer(&'writer self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn lines(buf: &SharedBuf) -> Vec<Value> {
+        String::from_utf8(buf.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn child_span_inherits_parent_fields() {
+        let buf = SharedBuf::default();
+        let subscriber = tracing_subscriber::registry().with(NdjsonLayer::new(buf.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let parent = tracing::info_span!("request", request_id = "abc-123");
+            let _parent = parent.enter();
+
+            let child = tracing::info_span!("handler");
+            let _child = child.enter();
+
+            tracing::info!(status = 200, "done");
+        });
+
+        let events = lines(&buf);
+        assert_eq!(events[0]["span"], "handler");
+        assert_eq!(events[0]["fields"]["request_id"], "abc-123");
+        assert_eq!(events[0]["fields"]["status"], 200);
+    }
+
+    #[test]
+    fn redaction_masks_and_drops_fields() {
+        let buf = SharedBuf::default();
+        let redaction = Redaction::new()
+            .mask_field("url")
+            .drop_field("authorization");
+        let layer = NdjsonLayer::new(buf.clone()).with_redaction(redaction);
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "request",
+                url = "https://example.com/secret",
+                authorization = "Bearer abc",
+            );
+            let _enter = span.enter();
+
+            tracing::info!("done");
+        });
+
+        let events = lines(&buf);
+        assert_eq!(events[0]["fields"]["url"], "***");
+        assert!(events[0]["fields"].get("authorization").is_none());
+    }
+
+    #[test]
+    fn unredacted_fields_pass_through_untouched() {
+        let buf = SharedBuf::default();
+        let subscriber = tracing_subscriber::registry().with(NdjsonLayer::new(buf.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(status = 200, "done");
+        });
+
+        let events = lines(&buf);
+        assert_eq!(events[0]["fields"]["status"], 200);
+        assert_eq!(events[0]["level"], "INFO");
+    }
+}
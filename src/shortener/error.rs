@@ -0,0 +1,72 @@
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use http::StatusCode;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShortenError {
+    #[error("{0}")]
+    BindException(String),
+    #[error("Connection/Accept failure: {0}")]
+    ClientConnectionFailure(String),
+    #[error("{0}")]
+    PrimaryKeyConflict(String),
+    #[error("DB Operation: {0}")]
+    DbOperationException(String),
+
+    #[error("Redirect URL not found")]
+    NotFound,
+}
+
+impl From<sqlx::Error> for ShortenError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::Database(err) if err.constraint() == Some("urls_pkey") => {
+                ShortenError::PrimaryKeyConflict(String::from("urls_pkey constraint violation"))
+            }
+            _ => ShortenError::DbOperationException(e.to_string()),
+        }
+    }
+}
+
+impl From<sled::Error> for ShortenError {
+    fn from(e: sled::Error) -> Self {
+        ShortenError::DbOperationException(e.to_string())
+    }
+}
+
+impl IntoResponse for ShortenError {
+    fn into_response(self) -> Response {
+        #[derive(serde::Serialize)]
+        struct ErrorResp<'a> {
+            message: &'a str,
+            code: &'a str,
+        }
+
+        let status = match self {
+            ShortenError::BindException(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ShortenError::ClientConnectionFailure(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ShortenError::PrimaryKeyConflict(_) => StatusCode::CONFLICT,
+            ShortenError::DbOperationException(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ShortenError::NotFound => StatusCode::NOT_FOUND,
+        };
+
+        let code = match self {
+            ShortenError::BindException(_) => "BIND_ERROR",
+            ShortenError::ClientConnectionFailure(_) => "CLIENT_CONNECTION_ERROR",
+            ShortenError::PrimaryKeyConflict(_) => "PRIMARY_KEY_CONFLICT",
+            ShortenError::DbOperationException(_) => "DB_OPERATION_ERROR",
+            ShortenError::NotFound => "NOT_FOUND",
+        };
+
+        (
+            status,
+            Json(ErrorResp {
+                message: self.to_string().as_str(),
+                code,
+            }),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,121 @@
+use super::error::ShortenError;
+
+/// How a short id is derived for a newly-shortened URL.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum IdStrategy {
+    /// Hash the url with `blake3` and base62-encode a prefix of the digest,
+    /// so the same url always maps to the same id and lookups by url become
+    /// pure idempotent reads instead of needing a conflict-retry loop.
+    #[default]
+    ContentAddressed,
+    /// Mint a random nanoid. Produces unguessable links, at the cost of the
+    /// caller having to retry on id collisions.
+    Random,
+}
+
+/// Number of digest bytes used for the first content-addressed id attempt.
+pub const INITIAL_ID_BYTES: usize = 4;
+
+/// Width, in base62 characters, of every derived id — matches the `CHAR(6)`
+/// column so growing `n_bytes` on a collision never produces an id that no
+/// longer fits the schema. Digest bytes beyond what `ID_WIDTH` base62 digits
+/// can hold just fold into the same fixed-width id via modular reduction.
+pub const ID_WIDTH: usize = 6;
+
+const BASE62_ALPHABET: &[u8; 62] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Digest bytes a blake3 hash can supply; the hard ceiling on how far the
+/// collision-extend loop in `insert_content_addressed` can grow `n_bytes`.
+const MAX_ID_BYTES: usize = blake3::OUT_LEN;
+
+/// Derives a short id for `url` by hashing it with blake3 and base62-encoding
+/// the first `n_bytes` of the digest to a fixed-width id. Deterministic: the
+/// same `(url, n_bytes)` pair always yields the same id.
+///
+/// Errors once `n_bytes` exceeds the digest length, so a caller stuck
+/// extending the prefix on repeated collisions fails loudly instead of
+/// panicking on an out-of-range slice.
+pub fn derive_id(url: &str, n_bytes: usize) -> Result<String, ShortenError> {
+    if n_bytes > MAX_ID_BYTES {
+        return Err(ShortenError::DbOperationException(format!(
+            "content-addressed id prefix exhausted after {n_bytes} bytes"
+        )));
+    }
+
+    let digest = blake3::hash(url.as_bytes());
+    Ok(base62_encode(&digest.as_bytes()[..n_bytes]))
+}
+
+fn base62_encode(bytes: &[u8]) -> String {
+    // Reduce modulo 62^ID_WIDTH on every byte rather than shifting the full
+    // digest into `value` first, so folding in more bytes than fit in a
+    // u128 (anything past MAX_ID_BYTES / 2) never overflows.
+    let modulus: u128 = 62u128.pow(ID_WIDTH as u32);
+    let mut value: u128 = 0;
+    for &b in bytes {
+        value = (value * 256 + b as u128) % modulus;
+    }
+
+    let mut out = vec![0u8; ID_WIDTH];
+    for slot in out.iter_mut().rev() {
+        *slot = BASE62_ALPHABET[(value % 62) as usize];
+        value /= 62;
+    }
+
+    String::from_utf8(out).expect("base62 alphabet is ascii")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_id_is_deterministic() {
+        let a = derive_id("https://example.com/a", INITIAL_ID_BYTES).unwrap();
+        let b = derive_id("https://example.com/a", INITIAL_ID_BYTES).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn derive_id_is_fixed_width() {
+        for n_bytes in [1, INITIAL_ID_BYTES, 16, MAX_ID_BYTES] {
+            let id = derive_id("https://example.com/a", n_bytes).unwrap();
+            assert_eq!(id.len(), ID_WIDTH, "n_bytes={n_bytes}");
+        }
+    }
+
+    #[test]
+    fn derive_id_extension_changes_the_id() {
+        // Not a proof that every prefix collision resolves, but pins down
+        // the invariant the collision-extend loop in insert_content_addressed
+        // depends on: growing n_bytes must not be a no-op. Checked across
+        // several urls rather than one fixed pair, since any single pair
+        // could in principle land on the same id by chance.
+        let urls = [
+            "https://example.com/a",
+            "https://example.com/b",
+            "https://example.com/c",
+            "https://example.com/d",
+            "https://example.com/e",
+        ];
+        let changed = urls.iter().any(|url| {
+            let short = derive_id(url, INITIAL_ID_BYTES).unwrap();
+            let longer = derive_id(url, INITIAL_ID_BYTES + 1).unwrap();
+            short != longer
+        });
+        assert!(changed, "extending n_bytes should change the id for at least one url");
+    }
+
+    #[test]
+    fn derive_id_rejects_a_prefix_past_the_digest() {
+        assert!(derive_id("https://example.com/a", MAX_ID_BYTES + 1).is_err());
+    }
+
+    #[test]
+    fn derive_id_does_not_panic_or_overflow_up_to_the_full_digest() {
+        for n_bytes in 1..=MAX_ID_BYTES {
+            derive_id("https://example.com/a", n_bytes).unwrap();
+        }
+    }
+}
@@ -0,0 +1,46 @@
+mod error;
+mod id;
+mod pg;
+mod sled_store;
+mod store;
+
+use std::{path::PathBuf, sync::Arc};
+
+pub use error::ShortenError;
+pub use id::IdStrategy;
+pub use pg::PgStore;
+pub use sled_store::SledStore;
+pub use store::ShortenStore;
+
+use crate::metrics::ShortenerMetrics;
+
+/// Which database backend to store urls in.
+#[derive(Debug, Clone)]
+pub enum StoreBackend {
+    Postgres(String),
+    Sled(PathBuf),
+}
+
+/// Selects the [`ShortenStore`] [`build`](StoreConfig::build) constructs at
+/// startup, plus the id-generation strategy it should use.
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    pub backend: StoreBackend,
+    pub id_strategy: IdStrategy,
+    pub metrics: ShortenerMetrics,
+}
+
+impl StoreConfig {
+    pub async fn build(self) -> Result<Arc<dyn ShortenStore>, ShortenError> {
+        match self.backend {
+            StoreBackend::Postgres(url) => Ok(Arc::new(
+                PgStore::try_new(&url, self.id_strategy, self.metrics).await?,
+            )),
+            StoreBackend::Sled(path) => Ok(Arc::new(SledStore::try_new(
+                path,
+                self.id_strategy,
+                self.metrics,
+            )?)),
+        }
+    }
+}
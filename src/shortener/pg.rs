@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use sqlx::{FromRow, PgPool};
+
+use super::{
+    error::ShortenError,
+    id::{derive_id, IdStrategy, INITIAL_ID_BYTES},
+    store::ShortenStore,
+};
+use crate::metrics::ShortenerMetrics;
+
+#[derive(Debug, FromRow)]
+struct UrlRecord {
+    #[sqlx(default)]
+    id: String,
+    #[sqlx(default)]
+    url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PgStore {
+    pool: PgPool,
+    id_strategy: IdStrategy,
+    metrics: ShortenerMetrics,
+}
+
+impl PgStore {
+    pub async fn try_new(
+        url: &str,
+        id_strategy: IdStrategy,
+        metrics: ShortenerMetrics,
+    ) -> Result<Self, ShortenError> {
+        let pool = PgPool::connect(url).await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS urls (
+                id CHAR(6) PRIMARY KEY,
+                url TEXT NOT NULL UNIQUE
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self {
+            pool,
+            id_strategy,
+            metrics,
+        })
+    }
+
+    async fn insert_random(&self, url: &str) -> Result<String, ShortenError> {
+        let id = nanoid::nanoid!(6);
+
+        let ret: UrlRecord = sqlx::query_as(
+            r#"
+            INSERT INTO urls (id, url) VALUES ($1, $2) on conflict(url) do update set url=excluded.url returning id
+            "#,
+        )
+        .bind(&id)
+        .bind(url)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(ret.id)
+    }
+
+    /// Derives a deterministic id from `url` and grows the digest prefix by
+    /// a byte whenever it's already taken by a *different* url, so two
+    /// distinct urls colliding on a truncated prefix still resolve to
+    /// distinct, stable ids.
+    ///
+    /// The insert itself is an `on conflict(url) do update` upsert rather
+    /// than a check-then-insert, so two concurrent shortens of the same new
+    /// url resolve to the same row instead of racing each other into a
+    /// unique-constraint error.
+    async fn insert_content_addressed(&self, url: &str) -> Result<String, ShortenError> {
+        let mut n_bytes = INITIAL_ID_BYTES;
+
+        loop {
+            let id = derive_id(url, n_bytes)?;
+
+            let inserted = sqlx::query_as::<_, UrlRecord>(
+                r#"
+                insert into urls (id, url) values ($1, $2)
+                on conflict (url) do update set url = excluded.url
+                returning id
+                "#,
+            )
+            .bind(&id)
+            .bind(url)
+            .fetch_one(&self.pool)
+            .await;
+
+            match inserted {
+                Ok(rec) => return Ok(rec.id),
+                Err(sqlx::Error::Database(err)) if err.constraint() == Some("urls_pkey") => {
+                    n_bytes += 1;
+                    self.metrics.id_retries.add(1, &[]);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ShortenStore for PgStore {
+    async fn insert(&self, url: &str) -> Result<String, ShortenError> {
+        if let Some(id) = self.lookup_by_url(url).await {
+            self.metrics.urls_looked_up.add(1, &[]);
+            return Ok(id);
+        }
+
+        let id = match self.id_strategy {
+            IdStrategy::Random => self.insert_random(url).await?,
+            IdStrategy::ContentAddressed => self.insert_content_addressed(url).await?,
+        };
+        self.metrics.urls_created.add(1, &[]);
+        Ok(id)
+    }
+
+    async fn lookup(&self, id: &str) -> Option<String> {
+        let ret: UrlRecord = sqlx::query_as("select url from urls where id = $1")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await
+            .ok()?;
+
+        Some(ret.url)
+    }
+
+    async fn lookup_by_url(&self, url: &str) -> Option<String> {
+        let ret: UrlRecord = sqlx::query_as("select id from urls where url = $1")
+            .bind(url)
+            .fetch_one(&self.pool)
+            .await
+            .ok()?;
+
+        Some(ret.id)
+    }
+}
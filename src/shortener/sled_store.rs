@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use async_trait::async_trait;
+use sled::{transaction::TransactionError, Transactional};
+
+use super::{
+    error::ShortenError,
+    id::{derive_id, IdStrategy, INITIAL_ID_BYTES},
+    store::ShortenStore,
+};
+use crate::metrics::ShortenerMetrics;
+
+const URL_TO_ID_TREE: &str = "url_to_id";
+
+/// Embedded backend with no external database to stand up. Keeps two trees
+/// — the default tree for `id -> url` and `url_to_id` for the reverse
+/// direction — updated together in a single transaction so a crash can never
+/// leave one direction pointing somewhere the other doesn't agree with.
+#[derive(Debug, Clone)]
+pub struct SledStore {
+    db: sled::Db,
+    id_strategy: IdStrategy,
+    metrics: ShortenerMetrics,
+}
+
+impl SledStore {
+    pub fn try_new(
+        path: impl AsRef<Path>,
+        id_strategy: IdStrategy,
+        metrics: ShortenerMetrics,
+    ) -> Result<Self, ShortenError> {
+        let db = sled::open(path)?;
+        Ok(Self {
+            db,
+            id_strategy,
+            metrics,
+        })
+    }
+
+    fn url_to_id(&self) -> Result<sled::Tree, ShortenError> {
+        Ok(self.db.open_tree(URL_TO_ID_TREE)?)
+    }
+
+    fn insert_random(&self, url: &str) -> Result<String, ShortenError> {
+        let id = nanoid::nanoid!(6);
+        if self.db.contains_key(&id)? {
+            return Err(ShortenError::PrimaryKeyConflict(format!(
+                "id {id} is already taken"
+            )));
+        }
+        self.write_pair(&id, url)?;
+        Ok(id)
+    }
+
+    /// Derives a deterministic id from `url` and grows the digest prefix by
+    /// a byte whenever it's already taken by a *different* url, so two
+    /// distinct urls colliding on a truncated prefix still resolve to
+    /// distinct, stable ids.
+    fn insert_content_addressed(&self, url: &str) -> Result<String, ShortenError> {
+        let mut n_bytes = INITIAL_ID_BYTES;
+
+        loop {
+            let id = derive_id(url, n_bytes)?;
+
+            match self.db.get(&id)? {
+                Some(existing) if existing.as_ref() == url.as_bytes() => return Ok(id),
+                Some(_) => {
+                    n_bytes += 1;
+                    self.metrics.id_retries.add(1, &[]);
+                    continue;
+                }
+                None => {
+                    self.write_pair(&id, url)?;
+                    return Ok(id);
+                }
+            }
+        }
+    }
+
+    fn write_pair(&self, id: &str, url: &str) -> Result<(), ShortenError> {
+        let url_to_id = self.url_to_id()?;
+        // `Transactional` is implemented over `&Tree` tuples, not `&Db` —
+        // deref `self.db` explicitly to its default tree so this is a
+        // `(&Tree, &Tree)` transaction.
+        (&*self.db, &url_to_id)
+            .transaction(|(id_to_url, url_to_id)| {
+                id_to_url.insert(id.as_bytes(), url.as_bytes())?;
+                url_to_id.insert(url.as_bytes(), id.as_bytes())?;
+                Ok(())
+            })
+            .map_err(|e: TransactionError<sled::Error>| {
+                ShortenError::DbOperationException(e.to_string())
+            })
+    }
+}
+
+#[async_trait]
+impl ShortenStore for SledStore {
+    async fn insert(&self, url: &str) -> Result<String, ShortenError> {
+        if let Some(id) = self.lookup_by_url(url).await {
+            self.metrics.urls_looked_up.add(1, &[]);
+            return Ok(id);
+        }
+
+        let id = match self.id_strategy {
+            IdStrategy::Random => self.insert_random(url)?,
+            IdStrategy::ContentAddressed => self.insert_content_addressed(url)?,
+        };
+        self.metrics.urls_created.add(1, &[]);
+        Ok(id)
+    }
+
+    async fn lookup(&self, id: &str) -> Option<String> {
+        let bytes = self.db.get(id).ok()??;
+        Some(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    async fn lookup_by_url(&self, url: &str) -> Option<String> {
+        let bytes = self.url_to_id().ok()?.get(url).ok()??;
+        Some(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use super::error::ShortenError;
+
+/// Storage backend for the url shortener. `shorten`/`get_url` are written
+/// purely against this trait so the service doesn't care whether ids live in
+/// Postgres or an embedded `sled` tree.
+#[async_trait]
+pub trait ShortenStore: Send + Sync + 'static {
+    /// Stores `url` and returns its id, minting a new one if `url` hasn't
+    /// been shortened before.
+    async fn insert(&self, url: &str) -> Result<String, ShortenError>;
+
+    /// Resolves an id to the url it was shortened from.
+    async fn lookup(&self, id: &str) -> Option<String>;
+
+    /// Resolves a url to the id it was previously shortened to, if any.
+    async fn lookup_by_url(&self, url: &str) -> Option<String>;
+}